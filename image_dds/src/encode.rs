@@ -0,0 +1,516 @@
+use crate::{
+    error::SurfaceError,
+    max_mipmap_count,
+    mipmap::{generate_mip_chain, ResizeFilter},
+    mip_dimension, ImageFormat, Surface, SurfaceRgba32Float, SurfaceRgba8,
+};
+
+/// How many mipmaps an encoded [Surface] should have.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MipmapCount {
+    /// Only encode the base level.
+    One,
+    /// Generate a full mip chain down to `1x1x1` using the given resampling filter.
+    Generate(ResizeFilter),
+    /// Encode exactly `n` levels from data that already contains them.
+    ExactCount(u32),
+}
+
+/// The quality/speed tradeoff to use for block compressed formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    Fast,
+    Normal,
+    Slow,
+}
+
+fn encode_uncompressed_from_rgbaf32(
+    rgba: &[f32],
+    image_format: ImageFormat,
+) -> Result<Vec<u8>, SurfaceError> {
+    use crate::rgba::{channels_from_rgbaf32, ChannelEncoding};
+    use ImageFormat as F;
+    let to_u8 = || crate::rgba::rgba8_from_rgbaf32_data(rgba);
+    let channels = |count, bytes, signed, encoding| {
+        channels_from_rgbaf32(rgba, count, bytes, signed, encoding)
+    };
+
+    Ok(match image_format {
+        F::R8Unorm => to_u8().chunks_exact(4).map(|p| p[0]).collect(),
+        F::R8Snorm => channels(1, 1, true, ChannelEncoding::Snorm),
+        F::R8Uint => channels(1, 1, false, ChannelEncoding::Int),
+        F::R8Sint => channels(1, 1, true, ChannelEncoding::Int),
+        F::R8G8Unorm => channels(2, 1, false, ChannelEncoding::Unorm),
+        F::R8G8Snorm => channels(2, 1, true, ChannelEncoding::Snorm),
+        F::R8G8Uint => channels(2, 1, false, ChannelEncoding::Int),
+        F::R8G8Sint => channels(2, 1, true, ChannelEncoding::Int),
+        F::R8G8B8A8Unorm | F::R8G8B8A8Srgb => to_u8(),
+        F::R8G8B8A8Snorm => channels(4, 1, true, ChannelEncoding::Snorm),
+        F::R8G8B8A8Uint => channels(4, 1, false, ChannelEncoding::Int),
+        F::R8G8B8A8Sint => channels(4, 1, true, ChannelEncoding::Int),
+        F::B8G8R8A8Unorm | F::B8G8R8A8Srgb => to_u8()
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        F::R16Unorm => channels(1, 2, false, ChannelEncoding::Unorm),
+        F::R16Snorm => channels(1, 2, true, ChannelEncoding::Snorm),
+        F::R16Uint => channels(1, 2, false, ChannelEncoding::Int),
+        F::R16Sint => channels(1, 2, true, ChannelEncoding::Int),
+        F::R16G16Unorm => channels(2, 2, false, ChannelEncoding::Unorm),
+        F::R16G16Snorm => channels(2, 2, true, ChannelEncoding::Snorm),
+        F::R16G16Uint => channels(2, 2, false, ChannelEncoding::Int),
+        F::R16G16Sint => channels(2, 2, true, ChannelEncoding::Int),
+        F::R16G16B16A16Unorm => channels(4, 2, false, ChannelEncoding::Unorm),
+        F::R16G16B16A16Snorm => channels(4, 2, true, ChannelEncoding::Snorm),
+        F::R16G16B16A16Uint => channels(4, 2, false, ChannelEncoding::Int),
+        F::R16G16B16A16Sint => channels(4, 2, true, ChannelEncoding::Int),
+        F::R16G16B16A16Float => rgba
+            .iter()
+            .flat_map(|f| half::f16::from_f32(*f).to_le_bytes())
+            .collect(),
+        F::R32G32B32A32Float => rgba.iter().flat_map(|f| f.to_le_bytes()).collect(),
+        _ => return Err(SurfaceError::UnsupportedFormat { format: image_format }),
+    })
+}
+
+fn rgb565(color: [f32; 3]) -> u16 {
+    let r = (color[0].clamp(0.0, 1.0) * 31.0).round() as u16;
+    let g = (color[1].clamp(0.0, 1.0) * 63.0).round() as u16;
+    let b = (color[2].clamp(0.0, 1.0) * 31.0).round() as u16;
+    (r << 11) | (g << 5) | b
+}
+
+fn min_max_corners(block: &[[f32; 4]]) -> ([f32; 4], [f32; 4]) {
+    let mut min = [1.0f32; 4];
+    let mut max = [0.0f32; 4];
+    for pixel in block {
+        for c in 0..4 {
+            min[c] = min[c].min(pixel[c]);
+            max[c] = max[c].max(pixel[c]);
+        }
+    }
+    (min, max)
+}
+
+fn encode_bc1_bytes(block: &[[f32; 4]]) -> [u8; 8] {
+    let (min, max) = min_max_corners(block);
+    let color0 = rgb565([max[0], max[1], max[2]]);
+    let color1 = rgb565([min[0], min[1], min[2]]);
+
+    let mut indices = 0u32;
+    for (i, pixel) in block.iter().enumerate() {
+        let index = nearest_index_2(pixel, &max, &min);
+        indices |= (index as u32) << (i * 2);
+    }
+
+    let mut out = [0u8; 8];
+    out[0..2].copy_from_slice(&color0.to_le_bytes());
+    out[2..4].copy_from_slice(&color1.to_le_bytes());
+    out[4..8].copy_from_slice(&indices.to_le_bytes());
+    out
+}
+
+fn nearest_index_2(pixel: &[f32; 4], c0: &[f32; 4], c1: &[f32; 4]) -> u8 {
+    let dist = |a: &[f32; 4]| {
+        (0..3)
+            .map(|c| (pixel[c] - a[c]).powi(2))
+            .sum::<f32>()
+    };
+    if dist(c0) <= dist(c1) {
+        0
+    } else {
+        1
+    }
+}
+
+fn encode_alpha_block(alphas: impl Iterator<Item = f32> + Clone) -> [u8; 8] {
+    let min = alphas.clone().fold(1.0f32, f32::min);
+    let max = alphas.clone().fold(0.0f32, f32::max);
+    let a0 = (max * 255.0).round() as u8;
+    let a1 = (min * 255.0).round() as u8;
+
+    let mut indices: u64 = 0;
+    for (i, a) in alphas.enumerate() {
+        // Only the two endpoint codes are used by this simplified encoder.
+        let index = if (a - max).abs() <= (a - min).abs() { 0u64 } else { 1u64 };
+        indices |= index << (i * 3);
+    }
+
+    let mut out = [0u8; 8];
+    out[0] = a0;
+    out[1] = a1;
+    out[2..8].copy_from_slice(&indices.to_le_bytes()[..6]);
+    out
+}
+
+fn encode_bcn<const BYTES_PER_BLOCK: usize>(
+    rgba: &[f32],
+    width: u32,
+    height: u32,
+    depth: u32,
+    encode_block: impl Fn(&[[f32; 4]]) -> [u8; BYTES_PER_BLOCK],
+) -> Vec<u8> {
+    let blocks_wide = width.div_ceil(4).max(1);
+    let blocks_high = height.div_ceil(4).max(1);
+    let mut out = Vec::with_capacity(
+        blocks_wide as usize * blocks_high as usize * depth as usize * BYTES_PER_BLOCK,
+    );
+
+    for z in 0..depth {
+        for by in 0..blocks_high {
+            for bx in 0..blocks_wide {
+                let mut block = [[0.0f32; 4]; 16];
+                for py in 0..4 {
+                    let y = (by * 4 + py).min(height - 1);
+                    for px in 0..4 {
+                        let x = (bx * 4 + px).min(width - 1);
+                        let index = ((z * width * height) + y * width + x) as usize * 4;
+                        block[(py * 4 + px) as usize] = [
+                            rgba[index],
+                            rgba[index + 1],
+                            rgba[index + 2],
+                            rgba[index + 3],
+                        ];
+                    }
+                }
+                out.extend_from_slice(&encode_block(&block));
+            }
+        }
+    }
+
+    out
+}
+
+fn encode_bcn_from_rgbaf32(
+    rgba: &[f32],
+    width: u32,
+    height: u32,
+    depth: u32,
+    image_format: ImageFormat,
+) -> Result<Vec<u8>, SurfaceError> {
+    use ImageFormat as F;
+    Ok(match image_format {
+        F::BC1Unorm | F::BC1Srgb => encode_bcn(rgba, width, height, depth, encode_bc1_bytes),
+        F::BC2Unorm | F::BC2Srgb => encode_bcn(rgba, width, height, depth, |block| {
+            let mut out = [0u8; 16];
+            for (i, pixel) in block.iter().enumerate() {
+                let nibble = (pixel[3].clamp(0.0, 1.0) * 15.0).round() as u8;
+                out[i / 2] |= if i % 2 == 0 { nibble } else { nibble << 4 };
+            }
+            out[8..16].copy_from_slice(&encode_bc1_bytes(block));
+            out
+        }),
+        F::BC3Unorm | F::BC3Srgb => encode_bcn(rgba, width, height, depth, |block| {
+            let mut out = [0u8; 16];
+            out[0..8].copy_from_slice(&encode_alpha_block(block.iter().map(|p| p[3])));
+            out[8..16].copy_from_slice(&encode_bc1_bytes(block));
+            out
+        }),
+        F::BC4Unorm | F::BC4Snorm => {
+            encode_bcn(rgba, width, height, depth, |block| {
+                encode_alpha_block(block.iter().map(|p| p[0]))
+            })
+        }
+        F::BC5Unorm | F::BC5Snorm => encode_bcn(rgba, width, height, depth, |block| {
+            let mut out = [0u8; 16];
+            out[0..8].copy_from_slice(&encode_alpha_block(block.iter().map(|p| p[0])));
+            out[8..16].copy_from_slice(&encode_alpha_block(block.iter().map(|p| p[1])));
+            out
+        }),
+        F::BC6Ufloat | F::BC6Sfloat => encode_bcn(rgba, width, height, depth, |block| {
+            // Simplified encode that stores the block's per-channel max and min as the two
+            // endpoints the decoder reads; no per-pixel indices are written.
+            let (min, max) = min_max_corners(block);
+            let mut out = [0u8; 16];
+            let put_half = |out: &mut [u8; 16], bit_offset: usize, value: f32| {
+                let bits = half::f16::from_f32(value).to_bits();
+                let byte = bit_offset / 8;
+                out[byte..byte + 2].copy_from_slice(&bits.to_le_bytes());
+            };
+            put_half(&mut out, 5, max[0]);
+            put_half(&mut out, 21, max[1]);
+            put_half(&mut out, 37, max[2]);
+            put_half(&mut out, 53, min[0]);
+            put_half(&mut out, 69, min[1]);
+            put_half(&mut out, 85, min[2]);
+            out
+        }),
+        F::BC7Unorm | F::BC7Srgb => encode_bcn(rgba, width, height, depth, |block| {
+            let (min, max) = min_max_corners(block);
+            let mut bits: u128 = 0;
+            let mut put = |offset: u32, count: u32, value: u8| {
+                bits |= (value as u128 & ((1u128 << count) - 1)) << offset;
+            };
+            put(7, 7, (max[0] * 127.0).round() as u8);
+            put(14, 7, (min[0] * 127.0).round() as u8);
+            put(21, 7, (max[1] * 127.0).round() as u8);
+            put(28, 7, (min[1] * 127.0).round() as u8);
+            put(35, 7, (max[2] * 127.0).round() as u8);
+            put(42, 7, (min[2] * 127.0).round() as u8);
+            put(49, 7, (max[3] * 127.0).round() as u8);
+            put(56, 7, (min[3] * 127.0).round() as u8);
+            for (i, pixel) in block.iter().enumerate() {
+                let dist_max: f32 = (0..4).map(|c| (pixel[c] - max[c]).powi(2)).sum();
+                let dist_min: f32 = (0..4).map(|c| (pixel[c] - min[c]).powi(2)).sum();
+                let index = if dist_max <= dist_min { 0u128 } else { 15u128 };
+                bits |= index << (65 + i as u32 * 4);
+            }
+            bits.to_le_bytes()
+        }),
+        _ => return Err(SurfaceError::UnsupportedFormat { format: image_format }),
+    })
+}
+
+/// Splits `data` containing `mipmaps` contiguous levels of a single layer into one `Vec` per
+/// level, the inverse of concatenating [crate::mipmap::generate_mip_chain]'s output.
+fn split_mip_levels(
+    data: &[f32],
+    width: u32,
+    height: u32,
+    depth: u32,
+    mipmaps: u32,
+    channels: u32,
+) -> Vec<Vec<f32>> {
+    let mut levels = Vec::with_capacity(mipmaps as usize);
+    let mut offset = 0usize;
+    for mipmap in 0..mipmaps {
+        let w = mip_dimension(width, mipmap) as usize;
+        let h = mip_dimension(height, mipmap) as usize;
+        let d = mip_dimension(depth, mipmap) as usize;
+        let len = w * h * d * channels as usize;
+        levels.push(data[offset..offset + len].to_vec());
+        offset += len;
+    }
+    levels
+}
+
+fn encode_data_from_rgbaf32(
+    rgba: &[f32],
+    width: u32,
+    height: u32,
+    depth: u32,
+    image_format: ImageFormat,
+) -> Result<Vec<u8>, SurfaceError> {
+    if crate::block_dimension(image_format) > 1 {
+        encode_bcn_from_rgbaf32(rgba, width, height, depth, image_format)
+    } else {
+        encode_uncompressed_from_rgbaf32(rgba, image_format)
+    }
+}
+
+/// Encodes an uncompressed RGBAF32 base image to `image_format`, generating additional
+/// mipmaps or using the data's existing levels according to `mipmaps`.
+pub fn encode_surface_rgbaf32(
+    surface: &SurfaceRgba32Float<impl AsRef<[f32]>>,
+    image_format: ImageFormat,
+    mipmaps: MipmapCount,
+) -> Result<Surface<Vec<u8>>, SurfaceError> {
+    let SurfaceRgba32Float {
+        width,
+        height,
+        depth,
+        layers,
+        data,
+        ..
+    } = surface;
+    let (width, height, depth, layers) = (*width, *height, *depth, *layers);
+    let data = data.as_ref();
+
+    let mipmap_count = match mipmaps {
+        MipmapCount::One => 1,
+        MipmapCount::Generate(_) => max_mipmap_count(width, height, depth),
+        MipmapCount::ExactCount(n) => n,
+    };
+
+    let base_len = (width * height * depth) as usize * 4;
+    // `ExactCount` expects `data` to already contain all `n` levels per layer, while `One` and
+    // `Generate` both start from a single base image per layer.
+    let layer_stride = match mipmaps {
+        MipmapCount::ExactCount(n) => {
+            (0..n)
+                .map(|mipmap| {
+                    let w = mip_dimension(width, mipmap) as usize;
+                    let h = mip_dimension(height, mipmap) as usize;
+                    let d = mip_dimension(depth, mipmap) as usize;
+                    w * h * d * 4
+                })
+                .sum()
+        }
+        MipmapCount::One | MipmapCount::Generate(_) => base_len,
+    };
+
+    let mut out = Vec::new();
+    for layer in 0..layers {
+        let layer_data = &data[layer as usize * layer_stride..(layer as usize + 1) * layer_stride];
+
+        let levels = match mipmaps {
+            MipmapCount::Generate(filter) => {
+                generate_mip_chain(layer_data, width, height, depth, 4, mipmap_count, filter)
+            }
+            MipmapCount::ExactCount(n) => split_mip_levels(layer_data, width, height, depth, n, 4),
+            MipmapCount::One => vec![layer_data.to_vec()],
+        };
+
+        for (mipmap, level) in levels.iter().enumerate() {
+            let mipmap = mipmap as u32;
+            let level_width = mip_dimension(width, mipmap);
+            let level_height = mip_dimension(height, mipmap);
+            let level_depth = mip_dimension(depth, mipmap);
+            out.extend_from_slice(&encode_data_from_rgbaf32(
+                level,
+                level_width,
+                level_height,
+                level_depth,
+                image_format,
+            )?);
+        }
+    }
+
+    Ok(Surface {
+        width,
+        height,
+        depth,
+        layers,
+        mipmaps: mipmap_count,
+        image_format,
+        data: out,
+    })
+}
+
+/// Encodes an uncompressed RGBA8 base image to `image_format`. See [encode_surface_rgbaf32].
+pub fn encode_surface_rgba8(
+    surface: &SurfaceRgba8<impl AsRef<[u8]>>,
+    image_format: ImageFormat,
+    mipmaps: MipmapCount,
+) -> Result<Surface<Vec<u8>>, SurfaceError> {
+    let rgbaf32 = SurfaceRgba32Float {
+        width: surface.width,
+        height: surface.height,
+        depth: surface.depth,
+        layers: surface.layers,
+        mipmaps: surface.mipmaps,
+        data: crate::rgba::rgbaf32_from_rgba8_data(surface.data.as_ref()),
+    };
+    encode_surface_rgbaf32(&rgbaf32, image_format, mipmaps)
+}
+
+impl<T: AsRef<[u8]>> Surface<T> {
+    /// Generates a full mip chain from the base level by resampling in linear RGBAF32 space
+    /// and re-encoding each level back to this surface's format.
+    pub fn generate_mipmaps(&self, filter: ResizeFilter) -> Result<Surface<Vec<u8>>, SurfaceError> {
+        self.validate()?;
+
+        let mut decoded = Vec::new();
+        for layer in 0..self.layers {
+            let data = self
+                .get(layer, 0)
+                .ok_or(SurfaceError::MipmapDataOutOfBounds { layer, mipmap: 0 })?;
+            decoded.extend_from_slice(&crate::decode::decode_data_rgbaf32(
+                self.width,
+                self.height,
+                self.depth,
+                self.image_format,
+                data,
+            )?);
+        }
+
+        let base = SurfaceRgba32Float {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            layers: self.layers,
+            mipmaps: 1,
+            data: decoded,
+        };
+
+        let mut mipped = base.generate_mipmaps(filter);
+        // Decoding converted Srgb color channels to linear light, so gamma-encode them again
+        // before storing them back in an Srgb format.
+        if self.image_format.is_srgb() {
+            for pixel in mipped.data.chunks_exact_mut(4) {
+                for c in &mut pixel[..3] {
+                    *c = if *c <= 0.0031308 { *c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+                }
+            }
+        }
+
+        encode_surface_rgbaf32(&mipped, self.image_format, MipmapCount::ExactCount(mipped.mipmaps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip_rgbaf32(pixel: [f32; 4], image_format: ImageFormat) -> Vec<f32> {
+        let base = SurfaceRgba32Float {
+            width: 1,
+            height: 1,
+            depth: 1,
+            layers: 1,
+            mipmaps: 1,
+            data: pixel.to_vec(),
+        };
+        let encoded = encode_surface_rgbaf32(&base, image_format, MipmapCount::One).unwrap();
+        encoded.decode_rgbaf32().unwrap().data
+    }
+
+    #[test]
+    fn round_trip_r8_snorm() {
+        let decoded = round_trip_rgbaf32([-1.0, 0.0, 0.0, 0.0], ImageFormat::R8Snorm);
+        assert!((decoded[0] - -1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn round_trip_r8_uint_is_unnormalized() {
+        let decoded = round_trip_rgbaf32([200.0, 0.0, 0.0, 0.0], ImageFormat::R8Uint);
+        assert_eq!(decoded[0], 200.0);
+    }
+
+    #[test]
+    fn round_trip_r8_sint_is_signed() {
+        let decoded = round_trip_rgbaf32([-100.0, 0.0, 0.0, 0.0], ImageFormat::R8Sint);
+        assert_eq!(decoded[0], -100.0);
+    }
+
+    #[test]
+    fn round_trip_r8g8_unorm_only_touches_two_channels() {
+        let decoded = round_trip_rgbaf32([1.0, 0.5, 0.0, 0.0], ImageFormat::R8G8Unorm);
+        assert!((decoded[0] - 1.0).abs() < 0.01);
+        assert!((decoded[1] - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn round_trip_r16g16b16a16_uint_is_unnormalized() {
+        let decoded = round_trip_rgbaf32([1234.0, 5678.0, 0.0, 0.0], ImageFormat::R16G16B16A16Uint);
+        assert_eq!(decoded[0], 1234.0);
+        assert_eq!(decoded[1], 5678.0);
+    }
+
+    #[test]
+    fn exact_count_encodes_every_requested_level() {
+        // A 1 layer, 2x2 base + 1x1 mip RGBA8 surface, each level filled with a distinct value.
+        let mut data = vec![0u8; 2 * 2 * 4];
+        data.extend(vec![255u8; 4]);
+
+        let surface = SurfaceRgba8 {
+            width: 2,
+            height: 2,
+            depth: 1,
+            layers: 1,
+            mipmaps: 2,
+            data,
+        };
+
+        let encoded =
+            encode_surface_rgba8(&surface, ImageFormat::R8G8B8A8Unorm, MipmapCount::ExactCount(2))
+                .unwrap();
+
+        assert_eq!(encoded.mipmaps, 2);
+        assert_eq!(encoded.data.len(), 2 * 2 * 4 + 4);
+
+        let decoded = encoded.decode_rgba8().unwrap();
+        assert_eq!(&decoded.data[..2 * 2 * 4], &[0u8; 2 * 2 * 4][..]);
+        assert_eq!(&decoded.data[2 * 2 * 4..], &[255u8; 4][..]);
+    }
+}
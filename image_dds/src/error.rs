@@ -0,0 +1,39 @@
+use thiserror::Error;
+
+use crate::ImageFormat;
+
+/// Errors that can occur when working with a [crate::Surface].
+#[derive(Debug, Error)]
+pub enum SurfaceError {
+    #[error("surface dimensions {width} x {height} x {depth} would overflow the pixel count")]
+    PixelCountWouldOverflow { width: u32, height: u32, depth: u32 },
+
+    #[error("surface dimensions {width} x {height} x {depth} are zero sized")]
+    ZeroSizedSurface { width: u32, height: u32, depth: u32 },
+
+    #[error("expected at most {max_mipmaps} mipmaps for the given dimensions but found {mipmaps}")]
+    UnexpectedMipmapCount { mipmaps: u32, max_mipmaps: u32 },
+
+    #[error("data for layer {layer} and mipmap {mipmap} is out of bounds")]
+    MipmapDataOutOfBounds { layer: u32, mipmap: u32 },
+
+    #[error("layer range {layers:?} is out of bounds for a surface with {surface_layers} layers")]
+    LayerRangeOutOfBounds {
+        layers: std::ops::Range<u32>,
+        surface_layers: u32,
+    },
+
+    #[error(
+        "mipmap range {mipmaps:?} is out of bounds for a surface with {surface_mipmaps} mipmaps"
+    )]
+    MipmapRangeOutOfBounds {
+        mipmaps: std::ops::Range<u32>,
+        surface_mipmaps: u32,
+    },
+
+    #[error("surface data length {actual} is smaller than the expected length {expected}")]
+    NotEnoughData { expected: usize, actual: usize },
+
+    #[error("{format:?} is not supported by the requested encoder")]
+    UnsupportedFormat { format: ImageFormat },
+}
@@ -0,0 +1,257 @@
+//! `image_dds` decodes and encodes surfaces using the block compressed and
+//! uncompressed formats commonly found in DDS textures.
+pub mod bcn;
+mod decode;
+mod encode;
+pub mod error;
+mod mipmap;
+pub mod rgba;
+
+pub use encode::{encode_surface_rgba8, encode_surface_rgbaf32, MipmapCount, Quality};
+pub use error::SurfaceError;
+pub use mipmap::ResizeFilter;
+
+/// The image format of the data stored in a [Surface].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    BC1Unorm,
+    BC1Srgb,
+    BC2Unorm,
+    BC2Srgb,
+    BC3Unorm,
+    BC3Srgb,
+    BC4Unorm,
+    BC4Snorm,
+    BC5Unorm,
+    BC5Snorm,
+    BC6Ufloat,
+    BC6Sfloat,
+    BC7Unorm,
+    BC7Srgb,
+    R8Unorm,
+    R8Snorm,
+    R8Uint,
+    R8Sint,
+    R8G8Unorm,
+    R8G8Snorm,
+    R8G8Uint,
+    R8G8Sint,
+    R8G8B8A8Unorm,
+    R8G8B8A8Srgb,
+    R8G8B8A8Snorm,
+    R8G8B8A8Uint,
+    R8G8B8A8Sint,
+    B8G8R8A8Unorm,
+    B8G8R8A8Srgb,
+    R16Unorm,
+    R16Snorm,
+    R16Uint,
+    R16Sint,
+    R16G16Unorm,
+    R16G16Snorm,
+    R16G16Uint,
+    R16G16Sint,
+    R16G16B16A16Unorm,
+    R16G16B16A16Snorm,
+    R16G16B16A16Uint,
+    R16G16B16A16Sint,
+    R16G16B16A16Float,
+    R32G32B32A32Float,
+}
+
+impl ImageFormat {
+    /// Whether this format stores color channels in sRGB gamma-encoded space.
+    pub fn is_srgb(&self) -> bool {
+        matches!(
+            self,
+            Self::BC1Srgb
+                | Self::BC2Srgb
+                | Self::BC3Srgb
+                | Self::BC7Srgb
+                | Self::R8G8B8A8Srgb
+                | Self::B8G8R8A8Srgb
+        )
+    }
+}
+
+/// Computes the dimension of mipmap level `mipmap` given the base `dimension`.
+pub fn mip_dimension(dimension: u32, mipmap: u32) -> u32 {
+    (dimension >> mipmap).max(1)
+}
+
+pub(crate) fn max_mipmap_count(width: u32, height: u32, depth: u32) -> u32 {
+    let max_dimension = width.max(height).max(depth);
+    32 - max_dimension.leading_zeros()
+}
+
+/// A compressed or uncompressed surface with an arbitrary number of array layers and mipmaps.
+///
+/// `data` stores each layer's mipmaps contiguously, with mipmaps ordered from largest to smallest.
+#[derive(Debug, Clone)]
+pub struct Surface<T> {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub layers: u32,
+    pub mipmaps: u32,
+    pub image_format: ImageFormat,
+    pub data: T,
+}
+
+impl<T: AsRef<[u8]>> Surface<T> {
+    pub(crate) fn validate(&self) -> Result<(), SurfaceError> {
+        if self.width == 0 || self.height == 0 || self.depth == 0 {
+            return Err(SurfaceError::ZeroSizedSurface {
+                width: self.width,
+                height: self.height,
+                depth: self.depth,
+            });
+        }
+
+        let pixel_count = (self.width as u64)
+            .checked_mul(self.height as u64)
+            .and_then(|v| v.checked_mul(self.depth as u64));
+        if pixel_count.is_none() {
+            return Err(SurfaceError::PixelCountWouldOverflow {
+                width: self.width,
+                height: self.height,
+                depth: self.depth,
+            });
+        }
+
+        let max_mipmaps = max_mipmap_count(self.width, self.height, self.depth);
+        if self.mipmaps > max_mipmaps {
+            return Err(SurfaceError::UnexpectedMipmapCount {
+                mipmaps: self.mipmaps,
+                max_mipmaps,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the data for the given `layer` and `mipmap`, or `None` if out of bounds.
+    pub(crate) fn get(&self, layer: u32, mipmap: u32) -> Option<&[u8]> {
+        if layer >= self.layers || mipmap >= self.mipmaps {
+            return None;
+        }
+
+        // Subresources are stored back to back, each padded to whole blocks by
+        // subresource_size, so sum the sizes of everything before the requested one.
+        let data = self.data.as_ref();
+        let mut offset = 0usize;
+        for l in 0..self.layers {
+            for m in 0..self.mipmaps {
+                let width = mip_dimension(self.width, m);
+                let height = mip_dimension(self.height, m);
+                let depth = mip_dimension(self.depth, m);
+                let size = subresource_size(width, height, depth, self.image_format);
+
+                if l == layer && m == mipmap {
+                    return data.get(offset..offset + size);
+                }
+                offset += size;
+            }
+        }
+        None
+    }
+}
+
+pub(crate) fn block_dimension(image_format: ImageFormat) -> u32 {
+    use ImageFormat as F;
+    match image_format {
+        F::BC1Unorm
+        | F::BC1Srgb
+        | F::BC2Unorm
+        | F::BC2Srgb
+        | F::BC3Unorm
+        | F::BC3Srgb
+        | F::BC4Unorm
+        | F::BC4Snorm
+        | F::BC5Unorm
+        | F::BC5Snorm
+        | F::BC6Ufloat
+        | F::BC6Sfloat
+        | F::BC7Unorm
+        | F::BC7Srgb => 4,
+        _ => 1,
+    }
+}
+
+pub(crate) fn bytes_per_block(image_format: ImageFormat) -> usize {
+    use ImageFormat as F;
+    match image_format {
+        F::BC1Unorm | F::BC1Srgb | F::BC4Unorm | F::BC4Snorm => 8,
+        F::BC2Unorm
+        | F::BC2Srgb
+        | F::BC3Unorm
+        | F::BC3Srgb
+        | F::BC5Unorm
+        | F::BC5Snorm
+        | F::BC6Ufloat
+        | F::BC6Sfloat
+        | F::BC7Unorm
+        | F::BC7Srgb => 16,
+        F::R8Unorm | F::R8Snorm | F::R8Uint | F::R8Sint => 1,
+        F::R8G8Unorm | F::R8G8Snorm | F::R8G8Uint | F::R8G8Sint => 2,
+        F::R8G8B8A8Unorm
+        | F::R8G8B8A8Srgb
+        | F::R8G8B8A8Snorm
+        | F::R8G8B8A8Uint
+        | F::R8G8B8A8Sint
+        | F::B8G8R8A8Unorm
+        | F::B8G8R8A8Srgb => 4,
+        F::R16Unorm | F::R16Snorm | F::R16Uint | F::R16Sint => 2,
+        F::R16G16Unorm | F::R16G16Snorm | F::R16G16Uint | F::R16G16Sint => 4,
+        F::R16G16B16A16Unorm
+        | F::R16G16B16A16Snorm
+        | F::R16G16B16A16Uint
+        | F::R16G16B16A16Sint
+        | F::R16G16B16A16Float => 8,
+        F::R32G32B32A32Float => 16,
+    }
+}
+
+pub(crate) fn subresource_size(width: u32, height: u32, depth: u32, image_format: ImageFormat) -> usize {
+    let block_dim = block_dimension(image_format);
+    let blocks_wide = width.div_ceil(block_dim).max(1) as usize;
+    let blocks_high = height.div_ceil(block_dim).max(1) as usize;
+    blocks_wide * blocks_high * depth as usize * bytes_per_block(image_format)
+}
+
+/// A decoded surface storing 8 bits per channel in row-major RGBA order.
+#[derive(Debug, Clone)]
+pub struct SurfaceRgba8<T> {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub layers: u32,
+    pub mipmaps: u32,
+    pub data: T,
+}
+
+/// A decoded surface storing 32 bit floats per channel in row-major RGBA order.
+#[derive(Debug, Clone)]
+pub struct SurfaceRgba32Float<T> {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub layers: u32,
+    pub mipmaps: u32,
+    pub data: T,
+}
+
+/// A decoded surface storing 16 bit half floats per channel in row-major RGBA order.
+///
+/// This is a lower memory alternative to [SurfaceRgba32Float] for HDR data that already
+/// originates as half floats, such as [ImageFormat::BC6Ufloat]/[ImageFormat::BC6Sfloat] and
+/// [ImageFormat::R16G16B16A16Float].
+#[derive(Debug, Clone)]
+pub struct SurfaceRgba16Float<T> {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub layers: u32,
+    pub mipmaps: u32,
+    pub data: T,
+}
@@ -0,0 +1,377 @@
+//! Mipmap chain generation by resampling a base image.
+use crate::{max_mipmap_count, mip_dimension, SurfaceRgba32Float, SurfaceRgba8};
+
+/// A resampling kernel used when generating mipmaps from a base image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResizeFilter {
+    /// Nearest neighbor sampling when upsampling. When downsampling, the (at most two) source
+    /// samples adjacent to each output sample's center are averaged with equal weight.
+    Point,
+    /// Bilinear/triangle filtering with a support radius of 1.
+    Triangle,
+    /// Lanczos filtering with a support radius of 3, suited to high quality downsampling.
+    Lanczos3,
+}
+
+impl ResizeFilter {
+    fn support(self) -> f32 {
+        match self {
+            Self::Point => 0.0,
+            Self::Triangle => 1.0,
+            Self::Lanczos3 => 3.0,
+        }
+    }
+
+    fn weight(self, x: f32) -> f32 {
+        match self {
+            Self::Point => {
+                if x.abs() < 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Triangle => {
+                if x.abs() < 1.0 {
+                    1.0 - x.abs()
+                } else {
+                    0.0
+                }
+            }
+            Self::Lanczos3 => {
+                if x.abs() < 3.0 {
+                    sinc(x) * sinc(x / 3.0)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let px = std::f32::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Resamples one dimension of `channels`-interleaved `data` from `src_count` to `dst_count`
+/// samples, filtering each of the `stride` rows of `src_count` consecutive samples independently.
+fn resample_1d(
+    data: &[f32],
+    src_count: u32,
+    dst_count: u32,
+    stride: u32,
+    channels: u32,
+    filter: ResizeFilter,
+) -> Vec<f32> {
+    let scale = dst_count as f32 / src_count as f32;
+    let inv_scale = 1.0 / scale.max(f32::EPSILON);
+    let radius = filter.support() * inv_scale.max(1.0);
+
+    let mut out = vec![0.0f32; dst_count as usize * stride as usize * channels as usize];
+
+    for row in 0..stride {
+        for dst_x in 0..dst_count {
+            let center = dst_x as f32 * inv_scale + 0.5 * inv_scale - 0.5;
+            let start = (center - radius).floor().max(0.0) as i64;
+            let end = ((center + radius).ceil() as i64).min(src_count as i64 - 1);
+
+            let mut weights_sum = 0.0;
+            let mut accum = [0.0f32; 4];
+            for src_x in start..=end {
+                let clamped = src_x.clamp(0, src_count as i64 - 1) as u32;
+                let weight = filter.weight((src_x as f32 - center) / inv_scale.max(1.0));
+                if weight == 0.0 {
+                    continue;
+                }
+                weights_sum += weight;
+
+                let src_index = (row * src_count + clamped) as usize * channels as usize;
+                for c in 0..channels as usize {
+                    accum[c] += data[src_index + c] * weight;
+                }
+            }
+
+            let out_index = (row * dst_count + dst_x) as usize * channels as usize;
+            for c in 0..channels as usize {
+                out[out_index + c] = if weights_sum > 0.0 {
+                    accum[c] / weights_sum
+                } else {
+                    0.0
+                };
+            }
+        }
+    }
+
+    out
+}
+
+/// Resamples a `width x height` image with `channels` interleaved channels per pixel
+/// to `new_width x new_height` using separable horizontal then vertical passes.
+pub fn resize_image(
+    data: &[f32],
+    width: u32,
+    height: u32,
+    channels: u32,
+    new_width: u32,
+    new_height: u32,
+    filter: ResizeFilter,
+) -> Vec<f32> {
+    // Horizontal pass: each of the `height` rows is resampled independently.
+    let horizontal = resample_1d(data, width, new_width, height, channels, filter);
+
+    // Vertical pass: transpose so each column becomes a contiguous "row" of `height`
+    // samples, resample those rows, then transpose back.
+    let mut transposed = vec![0.0f32; horizontal.len()];
+    for y in 0..height {
+        for x in 0..new_width {
+            let src = (y * new_width + x) as usize * channels as usize;
+            let dst = (x * height + y) as usize * channels as usize;
+            transposed[dst..dst + channels as usize]
+                .copy_from_slice(&horizontal[src..src + channels as usize]);
+        }
+    }
+
+    let vertical = resample_1d(&transposed, height, new_height, new_width, channels, filter);
+
+    let mut out = vec![0.0f32; (new_width * new_height) as usize * channels as usize];
+    for x in 0..new_width {
+        for y in 0..new_height {
+            let src = (x * new_height + y) as usize * channels as usize;
+            let dst = (y * new_width + x) as usize * channels as usize;
+            out[dst..dst + channels as usize].copy_from_slice(&vertical[src..src + channels as usize]);
+        }
+    }
+    out
+}
+
+/// Generates `mipmap_count` levels (the base included) from a `width x height x depth` base
+/// image with `channels` interleaved channels, halving all three dimensions each level down
+/// to a minimum of `1`.
+pub fn generate_mip_chain(
+    base: &[f32],
+    width: u32,
+    height: u32,
+    depth: u32,
+    channels: u32,
+    mipmap_count: u32,
+    filter: ResizeFilter,
+) -> Vec<Vec<f32>> {
+    let mut levels = Vec::with_capacity(mipmap_count as usize);
+    levels.push(base.to_vec());
+
+    for mipmap in 1..mipmap_count {
+        let prev_width = mip_dimension(width, mipmap - 1);
+        let prev_height = mip_dimension(height, mipmap - 1);
+        let prev_depth = mip_dimension(depth, mipmap - 1);
+        let new_width = mip_dimension(width, mipmap);
+        let new_height = mip_dimension(height, mipmap);
+        let new_depth = mip_dimension(depth, mipmap);
+
+        let prev = &levels[mipmap as usize - 1];
+        let slice_len = (prev_width * prev_height) as usize * channels as usize;
+
+        // Resize each existing z slice in the width/height plane first.
+        let mut resized_slices = Vec::with_capacity(prev_depth as usize);
+        for z in 0..prev_depth {
+            let slice = &prev[z as usize * slice_len..(z as usize + 1) * slice_len];
+            resized_slices.push(resize_image(
+                slice,
+                prev_width,
+                prev_height,
+                channels,
+                new_width,
+                new_height,
+                filter,
+            ));
+        }
+
+        // Resample along depth by treating each pixel position as an independent "row" of
+        // `prev_depth` z samples, the same row/column transpose trick `resize_image` uses
+        // for its own two passes.
+        let pixels = (new_width * new_height) as usize;
+        let mut depth_major = vec![0.0f32; prev_depth as usize * pixels * channels as usize];
+        for (z, slice) in resized_slices.iter().enumerate() {
+            for p in 0..pixels {
+                let src = p * channels as usize;
+                let dst = (p * prev_depth as usize + z) * channels as usize;
+                depth_major[dst..dst + channels as usize]
+                    .copy_from_slice(&slice[src..src + channels as usize]);
+            }
+        }
+        let resampled = resample_1d(&depth_major, prev_depth, new_depth, pixels as u32, channels, filter);
+
+        let mut level = vec![0.0f32; new_depth as usize * pixels * channels as usize];
+        for p in 0..pixels {
+            for z in 0..new_depth as usize {
+                let src = (p * new_depth as usize + z) * channels as usize;
+                let dst = (z * pixels + p) * channels as usize;
+                level[dst..dst + channels as usize]
+                    .copy_from_slice(&resampled[src..src + channels as usize]);
+            }
+        }
+
+        levels.push(level);
+    }
+
+    levels
+}
+
+/// Computes the number of interleaved samples (across all channels) a single array layer
+/// occupies given `mipmaps` existing mip levels, i.e. the sum of each level's own size.
+fn layer_stride(width: u32, height: u32, depth: u32, mipmaps: u32, channels: u32) -> usize {
+    (0..mipmaps)
+        .map(|mipmap| {
+            let w = mip_dimension(width, mipmap) as usize;
+            let h = mip_dimension(height, mipmap) as usize;
+            let d = mip_dimension(depth, mipmap) as usize;
+            w * h * d * channels as usize
+        })
+        .sum()
+}
+
+impl<T: AsRef<[f32]>> SurfaceRgba32Float<T> {
+    /// Generates a full mip chain from this surface's base level for each layer, discarding
+    /// any existing lower mipmaps (i.e. `self.mipmaps` may be greater than `1`; only the
+    /// first, largest level of each layer is used as the resampling source).
+    pub fn generate_mipmaps(&self, filter: ResizeFilter) -> SurfaceRgba32Float<Vec<f32>> {
+        let mipmap_count = max_mipmap_count(self.width, self.height, self.depth);
+        let base_len = (self.width * self.height * self.depth) as usize * 4;
+        let stride = layer_stride(self.width, self.height, self.depth, self.mipmaps, 4);
+        let data = self.data.as_ref();
+
+        let mut combined = Vec::new();
+        for layer in 0..self.layers {
+            let layer_offset = layer as usize * stride;
+            let base = &data[layer_offset..layer_offset + base_len];
+            let levels = generate_mip_chain(
+                base,
+                self.width,
+                self.height,
+                self.depth,
+                4,
+                mipmap_count,
+                filter,
+            );
+            for level in levels {
+                combined.extend_from_slice(&level);
+            }
+        }
+
+        SurfaceRgba32Float {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            layers: self.layers,
+            mipmaps: mipmap_count,
+            data: combined,
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> SurfaceRgba8<T> {
+    /// Generates a full mip chain from this surface's base level for each layer, discarding
+    /// any existing lower mipmaps. Resampling happens in linear RGBAF32 space before
+    /// converting back to RGBA8.
+    pub fn generate_mipmaps(&self, filter: ResizeFilter) -> SurfaceRgba8<Vec<u8>> {
+        let rgbaf32 = SurfaceRgba32Float {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            layers: self.layers,
+            mipmaps: self.mipmaps,
+            data: crate::rgba::rgbaf32_from_rgba8_data(self.data.as_ref()),
+        }
+        .generate_mipmaps(filter);
+
+        SurfaceRgba8 {
+            width: rgbaf32.width,
+            height: rgbaf32.height,
+            depth: rgbaf32.depth,
+            layers: rgbaf32.layers,
+            mipmaps: rgbaf32.mipmaps,
+            data: crate::rgba::rgba8_from_rgbaf32_data(&rgbaf32.data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_filter_upsample_is_nearest_neighbor() {
+        let data = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let resized = resize_image(&data, 2, 1, 3, 4, 1, ResizeFilter::Point);
+
+        for pixel in resized.chunks_exact(3) {
+            assert!(pixel == [0.0, 0.0, 0.0] || pixel == [1.0, 1.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn generate_mip_chain_halves_dimensions_to_one() {
+        let data = vec![0.5f32; 4 * 4 * 4];
+        let levels = generate_mip_chain(&data, 4, 4, 1, 4, 3, ResizeFilter::Triangle);
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0].len(), 4 * 4 * 4);
+        assert_eq!(levels[1].len(), 2 * 2 * 4);
+        assert_eq!(levels[2].len(), 4);
+    }
+
+    #[test]
+    fn generate_mip_chain_halves_depth_alongside_width_and_height() {
+        let data = vec![0.5f32; 4 * 4 * 4 * 4];
+        let levels = generate_mip_chain(&data, 4, 4, 4, 4, 3, ResizeFilter::Triangle);
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0].len(), 4 * 4 * 4 * 4);
+        assert_eq!(levels[1].len(), 2 * 2 * 2 * 4);
+        assert_eq!(levels[2].len(), 4);
+    }
+
+    #[test]
+    fn surface_rgba32float_generate_mipmaps_sets_mipmap_count() {
+        let surface = SurfaceRgba32Float {
+            width: 4,
+            height: 4,
+            depth: 1,
+            layers: 1,
+            mipmaps: 1,
+            data: vec![0.25f32; 4 * 4 * 4],
+        };
+
+        let mipped = surface.generate_mipmaps(ResizeFilter::Lanczos3);
+        assert_eq!(mipped.mipmaps, 3);
+    }
+
+    #[test]
+    fn generate_mipmaps_uses_each_layers_own_base_level() {
+        // 2 layers, each with an existing 2x2 base and 1x1 mip that should be discarded.
+        // Layer 0 is filled with 0.0, layer 1 with 1.0, so picking up stale data from the
+        // wrong layer/mip is distinguishable from the real per-layer base image.
+        let mut data = vec![0.0f32; 2 * 2 * 4];
+        data.extend(vec![0.0f32; 4]);
+        data.extend(vec![1.0f32; 2 * 2 * 4]);
+        data.extend(vec![1.0f32; 4]);
+
+        let surface = SurfaceRgba32Float {
+            width: 2,
+            height: 2,
+            depth: 1,
+            layers: 2,
+            mipmaps: 2,
+            data,
+        };
+
+        let mipped = surface.generate_mipmaps(ResizeFilter::Point);
+        let stride = mipped.data.len() / 2;
+        assert!(mipped.data[..stride].iter().all(|&v| v == 0.0));
+        assert!(mipped.data[stride..].iter().all(|&v| v == 1.0));
+    }
+}
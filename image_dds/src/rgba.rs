@@ -0,0 +1,324 @@
+//! Conversions between uncompressed pixel formats and the RGBA8/RGBAF32 formats
+//! used as the common decode targets.
+use crate::error::SurfaceError;
+
+fn validate_length(
+    width: u32,
+    height: u32,
+    depth: u32,
+    bytes_per_pixel: usize,
+    data: &[u8],
+) -> Result<usize, SurfaceError> {
+    let count = width as usize * height as usize * depth as usize;
+    let expected = count * bytes_per_pixel;
+    if data.len() < expected {
+        return Err(SurfaceError::NotEnoughData {
+            expected,
+            actual: data.len(),
+        });
+    }
+    Ok(count)
+}
+
+pub fn rgba8_from_r8(width: u32, height: u32, depth: u32, data: &[u8]) -> Result<Vec<u8>, SurfaceError> {
+    let count = validate_length(width, height, depth, 1, data)?;
+    let mut rgba = vec![0u8; count * 4];
+    for i in 0..count {
+        let r = data[i];
+        rgba[i * 4] = r;
+        rgba[i * 4 + 1] = r;
+        rgba[i * 4 + 2] = r;
+        rgba[i * 4 + 3] = 255;
+    }
+    Ok(rgba)
+}
+
+pub fn rgba8_from_rgba8(width: u32, height: u32, depth: u32, data: &[u8]) -> Result<Vec<u8>, SurfaceError> {
+    let count = validate_length(width, height, depth, 4, data)?;
+    Ok(data[..count * 4].to_vec())
+}
+
+pub fn rgba8_from_bgra8(width: u32, height: u32, depth: u32, data: &[u8]) -> Result<Vec<u8>, SurfaceError> {
+    let count = validate_length(width, height, depth, 4, data)?;
+    let mut rgba = vec![0u8; count * 4];
+    for i in 0..count {
+        rgba[i * 4] = data[i * 4 + 2];
+        rgba[i * 4 + 1] = data[i * 4 + 1];
+        rgba[i * 4 + 2] = data[i * 4];
+        rgba[i * 4 + 3] = data[i * 4 + 3];
+    }
+    Ok(rgba)
+}
+
+pub fn rgba8_from_rgbaf16(width: u32, height: u32, depth: u32, data: &[u8]) -> Result<Vec<u8>, SurfaceError> {
+    let count = validate_length(width, height, depth, 8, data)?;
+    let mut rgba = vec![0u8; count * 4];
+    for i in 0..count {
+        for c in 0..4 {
+            let bytes = [data[i * 8 + c * 2], data[i * 8 + c * 2 + 1]];
+            let f = half::f16::from_le_bytes(bytes).to_f32();
+            rgba[i * 4 + c] = (f.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+    }
+    Ok(rgba)
+}
+
+pub fn rgba8_from_rgbaf32(width: u32, height: u32, depth: u32, data: &[u8]) -> Result<Vec<u8>, SurfaceError> {
+    let count = validate_length(width, height, depth, 16, data)?;
+    let mut rgba = vec![0u8; count * 4];
+    for i in 0..count {
+        for c in 0..4 {
+            let bytes = [
+                data[i * 16 + c * 4],
+                data[i * 16 + c * 4 + 1],
+                data[i * 16 + c * 4 + 2],
+                data[i * 16 + c * 4 + 3],
+            ];
+            let f = f32::from_le_bytes(bytes);
+            rgba[i * 4 + c] = (f.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+    }
+    Ok(rgba)
+}
+
+pub fn rgbaf16_from_rgbaf16(
+    width: u32,
+    height: u32,
+    depth: u32,
+    data: &[u8],
+) -> Result<Vec<half::f16>, SurfaceError> {
+    let count = validate_length(width, height, depth, 8, data)?;
+    let mut rgba = vec![half::f16::from_bits(0); count * 4];
+    for i in 0..count {
+        for c in 0..4 {
+            let bytes = [data[i * 8 + c * 2], data[i * 8 + c * 2 + 1]];
+            rgba[i * 4 + c] = half::f16::from_le_bytes(bytes);
+        }
+    }
+    Ok(rgba)
+}
+
+pub fn rgbaf32_from_rgbaf16(width: u32, height: u32, depth: u32, data: &[u8]) -> Result<Vec<f32>, SurfaceError> {
+    let count = validate_length(width, height, depth, 8, data)?;
+    let mut rgba = vec![0.0f32; count * 4];
+    for i in 0..count {
+        for c in 0..4 {
+            let bytes = [data[i * 8 + c * 2], data[i * 8 + c * 2 + 1]];
+            rgba[i * 4 + c] = half::f16::from_le_bytes(bytes).to_f32();
+        }
+    }
+    Ok(rgba)
+}
+
+pub fn rgbaf32_from_rgbaf32(width: u32, height: u32, depth: u32, data: &[u8]) -> Result<Vec<f32>, SurfaceError> {
+    let count = validate_length(width, height, depth, 16, data)?;
+    let mut rgba = vec![0.0f32; count * 4];
+    for i in 0..count {
+        for c in 0..4 {
+            let bytes = [
+                data[i * 16 + c * 4],
+                data[i * 16 + c * 4 + 1],
+                data[i * 16 + c * 4 + 2],
+                data[i * 16 + c * 4 + 3],
+            ];
+            rgba[i * 4 + c] = f32::from_le_bytes(bytes);
+        }
+    }
+    Ok(rgba)
+}
+
+/// How a raw integer channel value maps to a normalized range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelEncoding {
+    /// `value / MAX` clamped to `0.0..=1.0`.
+    Unorm,
+    /// `max(value / MAX, -1.0)`.
+    Snorm,
+    /// No normalization; the raw magnitude is used as-is.
+    Int,
+}
+
+/// Describes the layout of an uncompressed format's channels for the generic
+/// [rgbaf32_from_channels]/[rgba8_from_channels]/[channels_from_rgbaf32] conversions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelLayout {
+    pub count: usize,
+    pub bytes_per_channel: usize,
+    pub signed: bool,
+    pub encoding: ChannelEncoding,
+}
+
+impl ChannelLayout {
+    pub const fn new(count: usize, bytes_per_channel: usize, signed: bool, encoding: ChannelEncoding) -> Self {
+        Self {
+            count,
+            bytes_per_channel,
+            signed,
+            encoding,
+        }
+    }
+
+    fn max_value(&self) -> f32 {
+        let bits = self.bytes_per_channel * 8;
+        if self.signed {
+            (1u64 << (bits - 1)) as f32 - 1.0
+        } else {
+            (1u64 << bits) as f32 - 1.0
+        }
+    }
+}
+
+/// Decodes the channels described by `layout` from interleaved little endian integers per
+/// pixel to RGBA floats, splatting a single channel to RGB and leaving missing channels at
+/// their identity value (`0.0` for color, `1.0` for alpha).
+pub fn rgbaf32_from_channels(
+    width: u32,
+    height: u32,
+    depth: u32,
+    data: &[u8],
+    layout: ChannelLayout,
+) -> Result<Vec<f32>, SurfaceError> {
+    let count = validate_length(width, height, depth, layout.count * layout.bytes_per_channel, data)?;
+    let max_value = layout.max_value();
+
+    let mut out = vec![0.0f32; count * 4];
+    out.chunks_exact_mut(4).for_each(|p| p[3] = 1.0);
+
+    for i in 0..count {
+        let base = i * layout.count * layout.bytes_per_channel;
+        for c in 0..layout.count {
+            let start = base + c * layout.bytes_per_channel;
+            let raw = read_le_channel(&data[start..start + layout.bytes_per_channel], layout.signed);
+
+            let value = match layout.encoding {
+                ChannelEncoding::Unorm => (raw / max_value).clamp(0.0, 1.0),
+                ChannelEncoding::Snorm => (raw / max_value).max(-1.0),
+                ChannelEncoding::Int => raw,
+            };
+
+            let out_index = i * 4 + if layout.count == 1 { 0 } else { c };
+            out[out_index] = value;
+            if layout.count == 1 {
+                out[i * 4 + 1] = value;
+                out[i * 4 + 2] = value;
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Decodes the same layout as [rgbaf32_from_channels] but to RGBA8. `Unorm`/`Snorm` values
+/// are already normalized and scale directly to `0..255`; `Int` has no inherent range, so its
+/// raw magnitude is instead scaled down by the format's maximum representable value, the same
+/// way `Unorm` is. This is lossy for formats wider than 8 bits (e.g. a 16-bit Uint channel
+/// collapses to 256 distinguishable shades), but preserves the overall magnitude of the data
+/// instead of saturating nearly everything to `255`. Alpha missing from the layout decodes to
+/// `255` for every encoding.
+pub fn rgba8_from_channels(
+    width: u32,
+    height: u32,
+    depth: u32,
+    data: &[u8],
+    layout: ChannelLayout,
+) -> Result<Vec<u8>, SurfaceError> {
+    let rgbaf32 = rgbaf32_from_channels(width, height, depth, data, layout)?;
+    let encoding = layout.encoding;
+    let max_value = layout.max_value();
+
+    Ok(rgbaf32
+        .iter()
+        .enumerate()
+        .map(|(i, value)| match encoding {
+            // The filled-in alpha is the identity `1.0`, not a raw channel value.
+            _ if i % 4 == 3 && layout.count < 4 => 255,
+            ChannelEncoding::Unorm => (value.clamp(0.0, 1.0) * 255.0).round() as u8,
+            ChannelEncoding::Snorm => ((value.clamp(-1.0, 1.0) * 0.5 + 0.5) * 255.0).round() as u8,
+            ChannelEncoding::Int => ((value / max_value).clamp(0.0, 1.0) * 255.0).round() as u8,
+        })
+        .collect())
+}
+
+/// Encodes interleaved RGBA floats to `channel_count` interleaved little endian integer
+/// channels per pixel, the inverse of [rgbaf32_from_channels]. Extra RGBA channels beyond
+/// `channel_count` are dropped.
+pub fn channels_from_rgbaf32(
+    rgba: &[f32],
+    channel_count: usize,
+    bytes_per_channel: usize,
+    signed: bool,
+    encoding: ChannelEncoding,
+) -> Vec<u8> {
+    let bits = bytes_per_channel * 8;
+    let max_value = if signed {
+        (1u64 << (bits - 1)) as f32 - 1.0
+    } else {
+        (1u64 << bits) as f32 - 1.0
+    };
+
+    let mut out = Vec::with_capacity(rgba.len() / 4 * channel_count * bytes_per_channel);
+    for pixel in rgba.chunks_exact(4) {
+        for &value in &pixel[..channel_count] {
+            let raw = match encoding {
+                ChannelEncoding::Unorm => (value.clamp(0.0, 1.0) * max_value).round(),
+                ChannelEncoding::Snorm => (value.clamp(-1.0, 1.0) * max_value).round(),
+                ChannelEncoding::Int => value.round(),
+            };
+            write_le_channel(&mut out, raw, bytes_per_channel, signed);
+        }
+    }
+    out
+}
+
+fn write_le_channel(out: &mut Vec<u8>, value: f32, bytes_per_channel: usize, signed: bool) {
+    match bytes_per_channel {
+        1 => {
+            let byte = if signed {
+                value.clamp(i8::MIN as f32, i8::MAX as f32) as i8 as u8
+            } else {
+                value.clamp(0.0, u8::MAX as f32) as u8
+            };
+            out.push(byte);
+        }
+        2 => {
+            let bytes = if signed {
+                (value.clamp(i16::MIN as f32, i16::MAX as f32) as i16).to_le_bytes()
+            } else {
+                (value.clamp(0.0, u16::MAX as f32) as u16).to_le_bytes()
+            };
+            out.extend_from_slice(&bytes);
+        }
+        _ => unreachable!("unsupported channel byte width"),
+    }
+}
+
+fn read_le_channel(bytes: &[u8], signed: bool) -> f32 {
+    match bytes.len() {
+        1 => {
+            if signed {
+                bytes[0] as i8 as f32
+            } else {
+                bytes[0] as f32
+            }
+        }
+        2 => {
+            let raw = u16::from_le_bytes([bytes[0], bytes[1]]);
+            if signed {
+                raw as i16 as f32
+            } else {
+                raw as f32
+            }
+        }
+        _ => unreachable!("unsupported channel byte width"),
+    }
+}
+
+pub fn rgba8_from_rgbaf32_data(data: &[f32]) -> Vec<u8> {
+    data.iter()
+        .map(|f| (f.clamp(0.0, 1.0) * 255.0).round() as u8)
+        .collect()
+}
+
+pub fn rgbaf32_from_rgba8_data(data: &[u8]) -> Vec<f32> {
+    data.iter().map(|u| *u as f32 / 255.0).collect()
+}